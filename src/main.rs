@@ -1,12 +1,192 @@
+use anyhow::{bail, Result};
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
+
+use notes::long::LongNote;
+use notes::note::{Note, NoteState};
+use notes::short::ShortNote;
 use notes::view::View;
 
 mod notes;
 
+#[derive(Parser)]
+#[command(name = "notes", about = "A small note/task tracker")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a new note without the interactive prompts
+    New {
+        /// Create a shorthand note with this title
+        #[arg(long, conflicts_with = "long")]
+        short: Option<String>,
+        /// Create a detailed note with this title
+        #[arg(long, conflicts_with = "short")]
+        long: Option<String>,
+        /// Optional due date (YYYY-MM-DD)
+        #[arg(long)]
+        due: Option<NaiveDate>,
+        /// Optional description, only valid alongside --long
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// Remove a note by 1-based index or title
+    Rm {
+        /// Index (as shown in `notes ls`) or case-insensitive title
+        query: String,
+    },
+    /// Edit fields of an existing note directly, without prompts
+    Edit {
+        /// Index (as shown in `notes ls`) or case-insensitive title
+        query: String,
+        #[arg(long)]
+        title: Option<String>,
+        #[arg(long)]
+        due: Option<NaiveDate>,
+        #[arg(long)]
+        state: Option<NoteState>,
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// List notes, optionally filtered
+    Ls {
+        #[arg(long)]
+        state: Option<NoteState>,
+        #[arg(long = "due-before")]
+        due_before: Option<NaiveDate>,
+    },
+    /// Print the note tree
+    Tree,
+}
+
 fn main() {
-    let mut view = View::new().expect("failed to load or create notes view");
-    match view.render() {
-        Err(e) => println!("Encountered error: {:?}", e),
-        _ => (),
+    let cli = Cli::parse();
+    let mut view = if cli.command.is_some() {
+        View::new_headless()
+    } else {
+        View::new()
+    }
+    .expect("failed to load or create notes view");
+
+    let result = match cli.command {
+        Some(command) => run_command(&mut view, command),
+        None => view.render(),
     };
+
+    let failed = result.is_err();
+    if let Err(e) = result {
+        println!("Encountered error: {:?}", e);
+    }
+
     view.save().expect("failed to save notes");
+
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+fn run_command(view: &mut View, command: Command) -> Result<()> {
+    match command {
+        Command::New {
+            short,
+            long,
+            due,
+            description,
+        } => cmd_new(view, short, long, due, description),
+        Command::Rm { query } => cmd_rm(view, &query),
+        Command::Edit {
+            query,
+            title,
+            due,
+            state,
+            description,
+        } => cmd_edit(view, &query, title, due, state, description),
+        Command::Ls { state, due_before } => cmd_ls(view, state, due_before),
+        Command::Tree => view.print_tree(),
+    }
+}
+
+fn cmd_new(
+    view: &mut View,
+    short: Option<String>,
+    long: Option<String>,
+    due: Option<NaiveDate>,
+    description: Option<String>,
+) -> Result<()> {
+    match (short, long) {
+        (Some(title), None) => {
+            if description.is_some() {
+                bail!("--description is only valid for --long notes");
+            }
+            view.add_note(Note::Short(ShortNote::new_headless(title, due)));
+        }
+        (None, Some(title)) => {
+            view.add_note(Note::Long(LongNote::new_headless(title, description, due)));
+        }
+        (None, None) => bail!("one of --short or --long is required"),
+        (Some(_), Some(_)) => bail!("only one of --short or --long may be given"),
+    };
+
+    Ok(())
+}
+
+fn cmd_rm(view: &mut View, query: &str) -> Result<()> {
+    let index = view
+        .find_note_index(query)
+        .ok_or_else(|| anyhow::anyhow!("no note matching '{}'", query))?;
+    let note = view.remove_note(index);
+    println!("Removed: {}", note.render());
+    Ok(())
+}
+
+fn cmd_edit(
+    view: &mut View,
+    query: &str,
+    title: Option<String>,
+    due: Option<NaiveDate>,
+    state: Option<NoteState>,
+    description: Option<String>,
+) -> Result<()> {
+    let index = view
+        .find_note_index(query)
+        .ok_or_else(|| anyhow::anyhow!("no note matching '{}'", query))?;
+    let note = view.note_mut(index).unwrap();
+
+    if let Some(title) = title {
+        note.set_title(title);
+    }
+    if let Some(due) = due {
+        note.set_due(Some(due));
+    }
+    if let Some(state) = state {
+        note.set_state(state);
+    }
+    if let Some(description) = description {
+        note.set_description(Some(description))?;
+    }
+
+    println!("Updated: {}", note.render());
+    Ok(())
+}
+
+fn cmd_ls(view: &View, state: Option<NoteState>, due_before: Option<NaiveDate>) -> Result<()> {
+    for (index, note) in view.notes().iter().enumerate() {
+        if let Some(ref state) = state {
+            if note.state() != state {
+                continue;
+            }
+        }
+        if let Some(due_before) = due_before {
+            match note.due_at() {
+                Some(due_at) if due_at < due_before => (),
+                _ => continue,
+            }
+        }
+        println!("{}. {}", index + 1, note.render());
+    }
+
+    Ok(())
 }
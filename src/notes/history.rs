@@ -0,0 +1,182 @@
+use uuid::Uuid;
+
+use super::note::Note;
+
+const MAX_HISTORY: usize = 50;
+
+/// A reversible edit to a `View`'s notes.
+///
+/// Applying an `Action` mutates the note list and returns the `Action`
+/// that undoes what was just done, which is how the same type serves both
+/// the undo and redo stacks. Notes are addressed by their stable `id`
+/// rather than a raw `Vec` index, so an action recorded before some other
+/// untracked structural change (e.g. a soft-delete into the trash) can't
+/// be replayed against the wrong note.
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub enum Action {
+    Remove(Uuid),
+    Insert(usize, Note),
+    Replace(Uuid, Note),
+}
+
+impl Action {
+    /// Apply this action, returning its inverse, or `None` if the note it
+    /// targets is no longer present, in which case the action is stale
+    /// and should simply be dropped rather than corrupting an unrelated
+    /// note.
+    fn apply(self, notes: &mut Vec<Note>) -> Option<Action> {
+        match self {
+            Action::Remove(id) => {
+                let index = notes.iter().position(|note| note.id() == id)?;
+                let removed = notes.remove(index);
+                Some(Action::Insert(index, removed))
+            }
+            Action::Insert(index, note) => {
+                let inverse = Action::Remove(note.id());
+                notes.insert(index.min(notes.len()), note);
+                Some(inverse)
+            }
+            Action::Replace(id, note) => {
+                let index = notes.iter().position(|note| note.id() == id)?;
+                let previous = std::mem::replace(&mut notes[index], note);
+                Some(Action::Replace(id, previous))
+            }
+        }
+    }
+}
+
+/// Bounded undo/redo stacks for a `View`.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+pub struct History {
+    undo_stack: Vec<Action>,
+    redo_stack: Vec<Action>,
+}
+
+impl History {
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Record an edit that was just made. Clears the redo stack, since a
+    /// fresh edit invalidates whatever was previously undone.
+    pub fn record(&mut self, inverse: Action) {
+        push_bounded(&mut self.undo_stack, inverse);
+        self.redo_stack.clear();
+    }
+
+    pub fn undo(&mut self, notes: &mut Vec<Note>) -> bool {
+        while let Some(action) = self.undo_stack.pop() {
+            if let Some(redo_action) = action.apply(notes) {
+                push_bounded(&mut self.redo_stack, redo_action);
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn redo(&mut self, notes: &mut Vec<Note>) -> bool {
+        while let Some(action) = self.redo_stack.pop() {
+            if let Some(undo_action) = action.apply(notes) {
+                push_bounded(&mut self.undo_stack, undo_action);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+fn push_bounded(stack: &mut Vec<Action>, action: Action) {
+    stack.push(action);
+    if stack.len() > MAX_HISTORY {
+        stack.remove(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notes::short::ShortNote;
+
+    fn note(title: &str) -> Note {
+        Note::Short(ShortNote::new_headless(title.to_string(), None))
+    }
+
+    #[test]
+    fn undo_add_removes_the_note() {
+        let mut notes = vec![note("a")];
+        let added_id = notes[0].id();
+        let mut history = History::default();
+        history.record(Action::Remove(added_id));
+
+        assert!(history.undo(&mut notes));
+        assert!(notes.is_empty());
+    }
+
+    #[test]
+    fn redo_restores_what_undo_removed() {
+        let mut notes = vec![note("a")];
+        let added_id = notes[0].id();
+        let mut history = History::default();
+        history.record(Action::Remove(added_id));
+
+        assert!(history.undo(&mut notes));
+        assert!(history.redo(&mut notes));
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].id(), added_id);
+    }
+
+    #[test]
+    fn undo_remove_reinserts_at_original_index() {
+        let mut notes = vec![note("a"), note("b")];
+        let removed = notes.remove(1);
+        let removed_id = removed.id();
+        let mut history = History::default();
+        history.record(Action::Insert(1, removed));
+
+        assert!(history.undo(&mut notes));
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[1].id(), removed_id);
+    }
+
+    #[test]
+    fn undo_replace_restores_previous_note() {
+        let mut notes = vec![note("a")];
+        let original_id = notes[0].id();
+        let previous = notes[0].clone();
+        notes[0].set_title("a-edited".to_string());
+        let mut history = History::default();
+        history.record(Action::Replace(original_id, previous));
+
+        assert!(history.undo(&mut notes));
+        assert_eq!(notes[0].title(), "a");
+    }
+
+    #[test]
+    fn stale_action_is_dropped_instead_of_corrupting_another_note() {
+        let mut notes = vec![note("a")];
+        let mut history = History::default();
+        // Target a note id that no longer exists in `notes`.
+        history.record(Action::Remove(Uuid::new_v4()));
+
+        assert!(!history.undo(&mut notes));
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].title(), "a");
+    }
+
+    #[test]
+    fn recording_a_fresh_edit_clears_the_redo_stack() {
+        let mut notes = vec![note("a")];
+        let added_id = notes[0].id();
+        let mut history = History::default();
+        history.record(Action::Remove(added_id));
+        history.undo(&mut notes);
+        assert!(history.can_redo());
+
+        history.record(Action::Remove(Uuid::new_v4()));
+        assert!(!history.can_redo());
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+
+use uuid::Uuid;
+
+use super::note::Note;
+
+/// The kind of relationship a `NoteLink` expresses between two notes.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum LinkKind {
+    Blocks,
+    DependsOn,
+    RelatesTo,
+}
+
+impl Display for LinkKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            LinkKind::Blocks => write!(f, "Blocks"),
+            LinkKind::DependsOn => write!(f, "Depends on"),
+            LinkKind::RelatesTo => write!(f, "Relates to"),
+        }
+    }
+}
+
+/// A typed reference from one note to another, identified by the target's
+/// stable `id` rather than its (unstable) index in `View.notes`.
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct NoteLink {
+    pub target_id: Uuid,
+    pub kind: LinkKind,
+}
+
+impl Display for NoteLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.kind, self.target_id)
+    }
+}
+
+/// The canonical `(blocker, blocked)` edges implied by every note's links.
+///
+/// `Blocks` and `DependsOn` describe the same kind of edge from opposite
+/// ends: "A Blocks B" and "B DependsOn A" both mean A must finish before
+/// B, i.e. a `(A, B)` edge. Normalising to this one direction keeps the
+/// graph walks below simple. `RelatesTo` carries no ordering and is
+/// skipped.
+fn blocker_edges(notes: &[Note]) -> Vec<(Uuid, Uuid)> {
+    notes
+        .iter()
+        .flat_map(|note| {
+            note.links().iter().filter_map(move |link| match link.kind {
+                LinkKind::Blocks => Some((note.id(), link.target_id)),
+                LinkKind::DependsOn => Some((link.target_id, note.id())),
+                LinkKind::RelatesTo => None,
+            })
+        })
+        .collect()
+}
+
+/// Would adding `new_link` from `from_id` introduce a cycle in the
+/// dependency graph? Normalises the proposed link to a `(blocker,
+/// blocked)` edge and checks whether `blocked` can already reach
+/// `blocker` through the existing edges, in which case the new edge
+/// would close a loop.
+pub fn creates_cycle(notes: &[Note], from_id: Uuid, new_link: &NoteLink) -> bool {
+    let (blocker, blocked) = match new_link.kind {
+        LinkKind::Blocks => (from_id, new_link.target_id),
+        LinkKind::DependsOn => (new_link.target_id, from_id),
+        LinkKind::RelatesTo => return false,
+    };
+    if blocker == blocked {
+        return true;
+    }
+
+    let edges = blocker_edges(notes);
+    let mut visited = HashSet::new();
+    let mut stack = vec![blocked];
+
+    while let Some(current) = stack.pop() {
+        if current == blocker {
+            return true;
+        }
+        if !visited.insert(current) {
+            continue;
+        }
+        for &(edge_blocker, edge_blocked) in &edges {
+            if edge_blocker == current {
+                stack.push(edge_blocked);
+            }
+        }
+    }
+
+    false
+}
+
+/// Walk the dependency edges and collect the ids of every note that
+/// transitively blocks `note_id`, directly or indirectly.
+pub fn transitive_blockers(notes: &[Note], note_id: Uuid) -> Vec<Uuid> {
+    let edges = blocker_edges(notes);
+    let mut visited = HashSet::new();
+    let mut stack = vec![note_id];
+    let mut blockers = Vec::new();
+
+    while let Some(current) = stack.pop() {
+        for &(edge_blocker, edge_blocked) in &edges {
+            if edge_blocked == current && visited.insert(edge_blocker) {
+                blockers.push(edge_blocker);
+                stack.push(edge_blocker);
+            }
+        }
+    }
+
+    blockers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notes::short::ShortNote;
+
+    fn note(title: &str) -> Note {
+        Note::Short(ShortNote::new_headless(title.to_string(), None))
+    }
+
+    #[test]
+    fn blocks_link_rejects_a_cycle() {
+        // a Blocks b, so proposing "b Blocks a" would close the loop.
+        let mut a = note("a");
+        let b = note("b");
+        a.add_link(NoteLink {
+            target_id: b.id(),
+            kind: LinkKind::Blocks,
+        });
+        let notes = [a.clone(), b.clone()];
+
+        let proposed = NoteLink {
+            target_id: a.id(),
+            kind: LinkKind::Blocks,
+        };
+        assert!(creates_cycle(&notes, b.id(), &proposed));
+    }
+
+    #[test]
+    fn depends_on_link_rejects_a_cycle() {
+        // b DependsOn a means a must finish before b, i.e. the same
+        // (a, b) edge as "a Blocks b"; proposing "a DependsOn b" (a
+        // (b, a) edge) would close the loop and should be rejected.
+        let a = note("a");
+        let mut b = note("b");
+        b.add_link(NoteLink {
+            target_id: a.id(),
+            kind: LinkKind::DependsOn,
+        });
+        let notes = [a.clone(), b.clone()];
+
+        let proposed = NoteLink {
+            target_id: b.id(),
+            kind: LinkKind::DependsOn,
+        };
+        assert!(creates_cycle(&notes, a.id(), &proposed));
+    }
+
+    #[test]
+    fn unrelated_link_does_not_create_a_cycle() {
+        let a = note("a");
+        let b = note("b");
+        let notes = [a.clone(), b.clone()];
+
+        let proposed = NoteLink {
+            target_id: b.id(),
+            kind: LinkKind::Blocks,
+        };
+        assert!(!creates_cycle(&notes, a.id(), &proposed));
+    }
+
+    #[test]
+    fn relates_to_is_never_a_cycle() {
+        let a = note("a");
+        let notes = [a.clone()];
+
+        let proposed = NoteLink {
+            target_id: a.id(),
+            kind: LinkKind::RelatesTo,
+        };
+        assert!(!creates_cycle(&notes, a.id(), &proposed));
+    }
+
+    #[test]
+    fn transitive_blockers_walks_the_full_chain() {
+        // a Blocks b Blocks c: both a and b transitively block c.
+        let mut a = note("a");
+        let mut b = note("b");
+        let c = note("c");
+        a.add_link(NoteLink {
+            target_id: b.id(),
+            kind: LinkKind::Blocks,
+        });
+        b.add_link(NoteLink {
+            target_id: c.id(),
+            kind: LinkKind::Blocks,
+        });
+        let notes = [a.clone(), b.clone(), c.clone()];
+
+        let mut blockers = transitive_blockers(&notes, c.id());
+        blockers.sort();
+        let mut expected = vec![a.id(), b.id()];
+        expected.sort();
+        assert_eq!(blockers, expected);
+    }
+}
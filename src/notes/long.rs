@@ -1,22 +1,30 @@
 use super::{
+    links::NoteLink,
     note::{Note, NoteState},
+    short::ShortNote,
     view::View,
 };
-use anyhow::{bail, Result};
+use anyhow::Result;
 use chrono::NaiveDate;
 use colored::{ColoredString, Colorize};
 use inquire::{Confirm, DateSelect, Editor, Select, Text};
+use ptree::TreeItem;
 use serde;
-use std::fmt::Display;
+use std::{borrow::Cow, fmt::Display};
+use uuid::Uuid;
 
 #[derive(serde::Deserialize, serde::Serialize, Clone)]
 pub struct LongNote {
+    #[serde(default = "Uuid::new_v4")]
+    pub id: Uuid,
     pub title: String,
     pub description: Option<String>,
     pub created_at: chrono::NaiveDate,
     pub due_at: Option<chrono::NaiveDate>,
     pub sub_notes: Option<Vec<Note>>,
     pub state: NoteState,
+    #[serde(default)]
+    pub links: Vec<NoteLink>,
 }
 
 enum UpdateChoice {
@@ -41,21 +49,76 @@ impl Display for UpdateChoice {
     }
 }
 
+impl TreeItem for LongNote {
+    type Child = Note;
+
+    fn write_self<W: std::io::Write>(
+        &self,
+        f: &mut W,
+        style: &ptree::Style,
+    ) -> std::io::Result<()> {
+        write!(f, "{}", style.paint(self.render()))
+    }
+
+    fn children(&self) -> Cow<'_, [Note]> {
+        match self.sub_notes {
+            Some(ref sub_notes) => Cow::from(sub_notes.clone()),
+            None => Cow::from(vec![]),
+        }
+    }
+}
+
 impl LongNote {
     pub fn new() -> Result<LongNote> {
         let title = Text::new("Enter note title:").prompt()?;
         let created_at = chrono::Utc::now().naive_local().date();
 
         Ok(LongNote {
-            title: title,
+            id: Uuid::new_v4(),
+            title,
             description: LongNote::maybe_add_description()?,
-            created_at: created_at,
+            created_at,
             sub_notes: None,
             due_at: LongNote::maybe_add_due_at()?,
             state: NoteState::Pending,
+            links: Vec::new(),
         })
     }
 
+    /// Build a `LongNote` directly, without any `inquire` prompts.
+    pub fn new_headless(
+        title: String,
+        description: Option<String>,
+        due_at: Option<NaiveDate>,
+    ) -> LongNote {
+        LongNote {
+            id: Uuid::new_v4(),
+            title,
+            description,
+            created_at: chrono::Utc::now().naive_local().date(),
+            sub_notes: None,
+            due_at,
+            state: NoteState::Pending,
+            links: Vec::new(),
+        }
+    }
+
+    /// Promote a `ShortNote` into a `LongNote`, carrying over its id,
+    /// title, dates, state and links so it can gain a description and
+    /// sub-notes without disturbing anything pointing at it.
+    pub fn from_short(short: ShortNote) -> LongNote {
+        LongNote {
+            id: short.id,
+            title: short.title,
+            description: None,
+            created_at: short.created_at,
+            sub_notes: None,
+            due_at: short.due_at,
+            state: short.state,
+            links: short.links,
+        }
+    }
+
     fn maybe_add_description() -> Result<Option<String>> {
         let with_description = Confirm::new("Add description?").prompt()?;
 
@@ -84,14 +147,32 @@ impl LongNote {
         let state_string = self.state.render();
         let base_string = format!("{}: {}: {}", state_string, self.title, self.created_at);
 
-        if let Some(due_at) = self.due_at {
+        let with_due = if let Some(due_at) = self.due_at {
             let due_at_string = format_due_at(&due_at);
             format!("{} due: {}", base_string, due_at_string)
         } else {
             base_string
+        };
+
+        match self.open_sub_note_count() {
+            0 => with_due,
+            1 => format!("{} (1 open sub-note)", with_due),
+            count => format!("{} ({} open sub-notes)", with_due, count),
         }
     }
 
+    fn open_sub_note_count(&self) -> usize {
+        self.sub_notes
+            .as_ref()
+            .map(|sub_notes| {
+                sub_notes
+                    .iter()
+                    .filter(|note| *note.state() != NoteState::Finished)
+                    .count()
+            })
+            .unwrap_or(0)
+    }
+
     pub fn update(&mut self) -> Result<()> {
         let choice_options = vec![
             UpdateChoice::Title,
@@ -128,7 +209,7 @@ impl LongNote {
         let new_description = Editor::new("Update description")
             .with_predefined_text(&predefined_text)
             .prompt()?;
-        if new_description.len() == 0 {
+        if new_description.is_empty() {
             self.description = None;
         } else {
             self.description = Some(new_description);
@@ -172,15 +253,13 @@ impl LongNote {
     }
 
     fn update_sub_notes(&mut self) -> Result<()> {
-        let mut view = match self.sub_notes.clone() {
-            Some(notes) => View::new_from_vec(notes),
-            None => View::new_from_vec(vec![]),
-        };
+        let notes = self.sub_notes.clone().unwrap_or_default();
+        let mut view = View::new_from_vec(&self.title, notes);
 
         println!("Viewing sub notes of: {}", self.render());
         view.render()?;
         let new_notes = view.get_notes();
-        if new_notes.len() > 0 {
+        if !new_notes.is_empty() {
             self.sub_notes = Some(new_notes);
         } else {
             self.sub_notes = None;
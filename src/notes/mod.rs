@@ -0,0 +1,8 @@
+pub mod history;
+pub mod links;
+pub mod long;
+pub mod note;
+pub mod search;
+pub mod short;
+pub mod storage;
+pub mod view;
@@ -1,11 +1,18 @@
 use std::fmt::Display;
+use std::str::FromStr;
 
-use super::{long::LongNote, short::ShortNote};
-use anyhow::Result;
+use super::{
+    links::NoteLink,
+    long::LongNote,
+    search::fuzzy_score,
+    short::{ShortNote, UpdateOutcome as ShortUpdateOutcome},
+};
+use anyhow::{bail, Result};
 use colored::{ColoredString, Colorize};
 use inquire::Select;
 use ptree::TreeItem;
 use serde;
+use uuid::Uuid;
 
 #[derive(serde::Deserialize, serde::Serialize, Clone)]
 pub enum Note {
@@ -26,7 +33,7 @@ impl TreeItem for Note {
         }
     }
 
-    fn children(&self) -> std::borrow::Cow<[Self::Child]> {
+    fn children(&self) -> std::borrow::Cow<'_, [Self::Child]> {
         match *self {
             Self::Long(ref note) => note.children(),
             Self::Short(ref note) => note.children(),
@@ -52,9 +59,128 @@ impl Note {
     }
 
     pub fn update(&mut self) -> Result<()> {
+        let promoted = match *self {
+            Note::Short(ref mut note) => match note.update()? {
+                ShortUpdateOutcome::Done => None,
+                ShortUpdateOutcome::Promote => Some(note.clone().promote()),
+            },
+            Note::Long(ref mut note) => {
+                note.update()?;
+                None
+            }
+        };
+
+        if let Some(promoted) = promoted {
+            *self = Note::Long(promoted);
+        }
+
+        Ok(())
+    }
+
+    pub fn title(&self) -> &str {
+        match *self {
+            Note::Short(ref note) => &note.title,
+            Note::Long(ref note) => &note.title,
+        }
+    }
+
+    pub fn state(&self) -> &NoteState {
+        match *self {
+            Note::Short(ref note) => &note.state,
+            Note::Long(ref note) => &note.state,
+        }
+    }
+
+    pub fn due_at(&self) -> Option<chrono::NaiveDate> {
+        match *self {
+            Note::Short(ref note) => note.due_at,
+            Note::Long(ref note) => note.due_at,
+        }
+    }
+
+    /// The stable id used to reference this note from a `NoteLink`,
+    /// independent of its position in `View.notes`.
+    pub fn id(&self) -> Uuid {
+        match *self {
+            Note::Short(ref note) => note.id,
+            Note::Long(ref note) => note.id,
+        }
+    }
+
+    pub fn links(&self) -> &[NoteLink] {
         match *self {
-            Note::Short(ref mut note) => note.update(),
-            Note::Long(ref mut note) => note.update(),
+            Note::Short(ref note) => &note.links,
+            Note::Long(ref note) => &note.links,
+        }
+    }
+
+    pub fn add_link(&mut self, link: NoteLink) {
+        match *self {
+            Note::Short(ref mut note) => note.links.push(link),
+            Note::Long(ref mut note) => note.links.push(link),
+        }
+    }
+
+    pub fn remove_link(&mut self, index: usize) {
+        match *self {
+            Note::Short(ref mut note) => {
+                note.links.remove(index);
+            }
+            Note::Long(ref mut note) => {
+                note.links.remove(index);
+            }
+        }
+    }
+
+    pub fn set_title(&mut self, title: String) {
+        match *self {
+            Note::Short(ref mut note) => note.title = title,
+            Note::Long(ref mut note) => note.title = title,
+        }
+    }
+
+    pub fn set_due(&mut self, due_at: Option<chrono::NaiveDate>) {
+        match *self {
+            Note::Short(ref mut note) => note.due_at = due_at,
+            Note::Long(ref mut note) => note.due_at = due_at,
+        }
+    }
+
+    pub fn set_state(&mut self, state: NoteState) {
+        match *self {
+            Note::Short(ref mut note) => note.state = state,
+            Note::Long(ref mut note) => note.state = state,
+        }
+    }
+
+    /// Fuzzy-match `query` against this note's title and, for `LongNote`s,
+    /// its description, returning the best of the two scores.
+    pub fn search_score(&self, query: &str) -> Option<i64> {
+        let title_score = fuzzy_score(query, self.title());
+        let description_score = match *self {
+            Note::Long(ref note) => note
+                .description
+                .as_deref()
+                .and_then(|description| fuzzy_score(query, description)),
+            Note::Short(_) => None,
+        };
+
+        match (title_score, description_score) {
+            (Some(title_score), Some(description_score)) => {
+                Some(title_score.max(description_score))
+            }
+            (Some(score), None) | (None, Some(score)) => Some(score),
+            (None, None) => None,
+        }
+    }
+
+    pub fn set_description(&mut self, description: Option<String>) -> Result<()> {
+        match *self {
+            Note::Long(ref mut note) => {
+                note.description = description;
+                Ok(())
+            }
+            Note::Short(_) => bail!("shorthand notes don't support descriptions"),
         }
     }
 }
@@ -73,7 +199,7 @@ impl Display for NoteType {
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Eq)]
 pub enum NoteState {
     Pending,
     Started,
@@ -81,6 +207,20 @@ pub enum NoteState {
     Deprioritised,
 }
 
+impl FromStr for NoteState {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "pending" => Ok(NoteState::Pending),
+            "started" => Ok(NoteState::Started),
+            "finished" => Ok(NoteState::Finished),
+            "deprioritised" | "deprioritized" => Ok(NoteState::Deprioritised),
+            _ => bail!("unrecognised note state: {}", s),
+        }
+    }
+}
+
 impl NoteState {
     pub fn render(&self) -> ColoredString {
         match *self {
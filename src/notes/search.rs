@@ -0,0 +1,97 @@
+const MATCH_SCORE: i64 = 10;
+const BOUNDARY_BONUS: i64 = 8;
+const CONSECUTIVE_BONUS: i64 = 12;
+const GAP_PENALTY: i64 = 1;
+
+/// Score how well `query` matches `candidate` as an ordered, possibly
+/// non-contiguous subsequence.
+///
+/// Each matched character earns a base score (`MATCH_SCORE`), plus a bonus if it falls on a
+/// word boundary (start of the string, or just after a space/`-`/`_`) or
+/// immediately continues a run of matched characters, and a small penalty
+/// for each candidate character skipped since the previous match. Returns
+/// `None` if `query` is not a subsequence of `candidate` at all.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let query_chars: Vec<char> = query.to_lowercase().chars().collect();
+    let candidate_chars: Vec<char> = candidate.to_lowercase().chars().collect();
+
+    let mut score = 0i64;
+    let mut query_index = 0;
+    let mut last_match_index: Option<usize> = None;
+
+    for (candidate_index, &candidate_char) in candidate_chars.iter().enumerate() {
+        if query_index >= query_chars.len() {
+            break;
+        }
+
+        if candidate_char != query_chars[query_index] {
+            continue;
+        }
+
+        score += MATCH_SCORE;
+
+        let at_boundary = candidate_index == 0
+            || matches!(candidate_chars[candidate_index - 1], ' ' | '-' | '_');
+        if at_boundary {
+            score += BOUNDARY_BONUS;
+        }
+
+        match last_match_index {
+            Some(previous) if previous + 1 == candidate_index => score += CONSECUTIVE_BONUS,
+            Some(previous) => score -= GAP_PENALTY * (candidate_index - previous - 1) as i64,
+            None => (),
+        }
+
+        last_match_index = Some(candidate_index);
+        query_index += 1;
+    }
+
+    if query_index == query_chars.len() {
+        Some(score)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_non_subsequence() {
+        assert_eq!(fuzzy_score("xyz", "hello world"), None);
+    }
+
+    #[test]
+    fn boundary_match_scores_higher_than_mid_word_match() {
+        // In the same candidate, "w" matches at the word boundary before
+        // "world" and "o" matches mid-word; both are single-character
+        // queries, so the boundary bonus is the only thing that can differ.
+        let boundary = fuzzy_score("w", "a world").unwrap();
+        let mid_word = fuzzy_score("o", "a world").unwrap();
+        assert!(boundary > mid_word);
+    }
+
+    #[test]
+    fn consecutive_run_scores_higher_than_same_length_gapped_match() {
+        let consecutive = fuzzy_score("ab", "ab").unwrap();
+        let gapped = fuzzy_score("ab", "a_b").unwrap();
+        assert!(consecutive > gapped);
+    }
+
+    #[test]
+    fn wider_gap_is_penalised_more() {
+        let small_gap = fuzzy_score("ab", "a_b").unwrap();
+        let big_gap = fuzzy_score("ab", "a__b").unwrap();
+        assert!(small_gap > big_gap);
+    }
+
+    #[test]
+    fn empty_query_matches_everything_with_zero_score() {
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+    }
+}
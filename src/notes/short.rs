@@ -1,23 +1,33 @@
-use super::note::{Note, NoteState};
+use super::{
+    links::NoteLink,
+    long::LongNote,
+    note::{Note, NoteState},
+};
 use anyhow::Result;
 use colored::{ColoredString, Colorize};
 use inquire::{Confirm, DateSelect, Select, Text};
 use ptree::TreeItem;
 use serde;
 use std::{borrow::Cow, fmt::Display};
+use uuid::Uuid;
 
 #[derive(serde::Deserialize, serde::Serialize, Clone)]
 pub struct ShortNote {
+    #[serde(default = "Uuid::new_v4")]
+    pub id: Uuid,
     pub title: String,
     pub created_at: chrono::NaiveDate,
     pub due_at: Option<chrono::NaiveDate>,
     pub state: NoteState,
+    #[serde(default)]
+    pub links: Vec<NoteLink>,
 }
 
 enum UpdateChoice {
     Title,
     Due,
     State,
+    Promote,
 }
 
 impl Display for UpdateChoice {
@@ -26,10 +36,20 @@ impl Display for UpdateChoice {
             UpdateChoice::Title => write!(f, "Change Title"),
             UpdateChoice::Due => write!(f, "Update or Set Due"),
             UpdateChoice::State => write!(f, "Update State"),
+            UpdateChoice::Promote => write!(f, "Promote to detailed note"),
         }
     }
 }
 
+/// What came out of a `ShortNote::update` call.
+pub enum UpdateOutcome {
+    Done,
+    /// The user asked to promote this note to a `LongNote` so it can carry
+    /// a description and sub-notes; the caller owns the actual conversion
+    /// since that requires replacing the enclosing `Note` variant.
+    Promote,
+}
+
 impl TreeItem for ShortNote {
     type Child = Note;
     fn write_self<W: std::io::Write>(
@@ -40,7 +60,7 @@ impl TreeItem for ShortNote {
         write!(f, "{}", style.paint(self.render()))
     }
 
-    fn children(&self) -> std::borrow::Cow<[Note]> {
+    fn children(&self) -> std::borrow::Cow<'_, [Note]> {
         Cow::from(vec![])
     }
 }
@@ -58,23 +78,35 @@ impl ShortNote {
         }
     }
 
+    /// Build a `ShortNote` directly, without any `inquire` prompts.
+    pub fn new_headless(title: String, due_at: Option<chrono::NaiveDate>) -> ShortNote {
+        match due_at {
+            Some(due_at) => ShortNote::new_with_deadline(title, due_at),
+            None => ShortNote::new_no_deadline(title),
+        }
+    }
+
     fn new_no_deadline(title: String) -> ShortNote {
         let now = chrono::Utc::now().naive_local().date();
         ShortNote {
-            title: title,
+            id: Uuid::new_v4(),
+            title,
             created_at: now,
             due_at: None,
             state: NoteState::Pending,
+            links: Vec::new(),
         }
     }
 
     fn new_with_deadline(title: String, due_at: chrono::NaiveDate) -> ShortNote {
         let now = chrono::Utc::now().naive_local().date();
         ShortNote {
-            title: title,
+            id: Uuid::new_v4(),
+            title,
             created_at: now,
             due_at: Some(due_at),
             state: NoteState::Pending,
+            links: Vec::new(),
         }
     }
 
@@ -90,17 +122,29 @@ impl ShortNote {
         }
     }
 
-    pub fn update(&mut self) -> Result<()> {
-        let choice_options = vec![UpdateChoice::Title, UpdateChoice::Due, UpdateChoice::State];
+    pub fn update(&mut self) -> Result<UpdateOutcome> {
+        let choice_options = vec![
+            UpdateChoice::Title,
+            UpdateChoice::Due,
+            UpdateChoice::State,
+            UpdateChoice::Promote,
+        ];
         let choice = Select::new("Choose how to update", choice_options).prompt()?;
 
         match choice {
-            UpdateChoice::Title => self.update_title(),
-            UpdateChoice::Due => self.update_due(),
-            UpdateChoice::State => self.update_state(),
+            UpdateChoice::Title => self.update_title().map(|()| UpdateOutcome::Done),
+            UpdateChoice::Due => self.update_due().map(|()| UpdateOutcome::Done),
+            UpdateChoice::State => self.update_state().map(|()| UpdateOutcome::Done),
+            UpdateChoice::Promote => Ok(UpdateOutcome::Promote),
         }
     }
 
+    /// Convert this note into a `LongNote` with no description or
+    /// sub-notes yet, carrying over its title, dates and state.
+    pub fn promote(self) -> LongNote {
+        LongNote::from_short(self)
+    }
+
     fn update_state(&mut self) -> Result<()> {
         let state_choices = vec![
             NoteState::Pending,
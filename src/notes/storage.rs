@@ -0,0 +1,216 @@
+use std::{env, fs, path::PathBuf};
+
+use anyhow::Result;
+use serde::{de::DeserializeOwned, Serialize};
+
+const VIEW_FILE_ENV: &str = "NOTES_FILE";
+const VIEW_FORMAT_ENV: &str = "NOTES_FORMAT";
+
+/// The on-disk encoding used to persist a `View`.
+///
+/// `Json` is always available; the others are gated behind cargo features
+/// so a build only pulls in the serde crates it actually needs.
+#[derive(Clone, Copy, Debug)]
+pub enum Format {
+    Json,
+    #[cfg(feature = "ron")]
+    Ron,
+    #[cfg(feature = "cbor")]
+    Cbor,
+    #[cfg(feature = "bincode")]
+    Bincode,
+}
+
+impl Format {
+    fn from_env() -> Format {
+        match env::var(VIEW_FORMAT_ENV).ok().as_deref() {
+            None => Format::Json,
+            Some("json") => Format::Json,
+            #[cfg(feature = "ron")]
+            Some("ron") => Format::Ron,
+            #[cfg(feature = "cbor")]
+            Some("cbor") => Format::Cbor,
+            #[cfg(feature = "bincode")]
+            Some("bincode") => Format::Bincode,
+            Some(other) => {
+                eprintln!(
+                    "unrecognised {}='{}', falling back to json",
+                    VIEW_FORMAT_ENV, other
+                );
+                Format::Json
+            }
+        }
+    }
+
+    fn default_path(self) -> &'static str {
+        match self {
+            Format::Json => "./notes_view.json",
+            #[cfg(feature = "ron")]
+            Format::Ron => "./notes_view.ron",
+            #[cfg(feature = "cbor")]
+            Format::Cbor => "./notes_view.cbor",
+            #[cfg(feature = "bincode")]
+            Format::Bincode => "./notes_view.bin",
+        }
+    }
+
+    fn encode<T: Serialize>(self, value: &T) -> Result<Vec<u8>> {
+        Ok(match self {
+            Format::Json => serde_json::to_vec(value)?,
+            #[cfg(feature = "ron")]
+            Format::Ron => ron::to_string(value)?.into_bytes(),
+            #[cfg(feature = "cbor")]
+            Format::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes)?;
+                bytes
+            }
+            #[cfg(feature = "bincode")]
+            Format::Bincode => bincode::serialize(value)?,
+        })
+    }
+
+    fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T> {
+        Ok(match self {
+            Format::Json => serde_json::from_slice(bytes)?,
+            #[cfg(feature = "ron")]
+            Format::Ron => ron::de::from_bytes(bytes)?,
+            #[cfg(feature = "cbor")]
+            Format::Cbor => ciborium::from_reader(bytes)?,
+            #[cfg(feature = "bincode")]
+            Format::Bincode => bincode::deserialize(bytes)?,
+        })
+    }
+}
+
+/// Reads and writes a serializable value to a configurable path, in a
+/// configurable format.
+///
+/// Both are picked up from the environment (`NOTES_FILE`, `NOTES_FORMAT`)
+/// so the same `View` can be kept as human-editable JSON/RON for everyday
+/// use, or as compact CBOR/bincode when size or speed matters.
+pub struct Storage {
+    path: PathBuf,
+    format: Format,
+}
+
+impl Storage {
+    pub fn from_env() -> Storage {
+        let format = Format::from_env();
+        let path = env::var(VIEW_FILE_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from(format.default_path()));
+
+        Storage { path, format }
+    }
+
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+
+    pub fn load<T: DeserializeOwned>(&self) -> Result<T> {
+        let bytes = fs::read(&self.path)?;
+        self.format.decode(&bytes)
+    }
+
+    pub fn save<T: Serialize>(&self, value: &T) -> Result<()> {
+        let bytes = self.format.encode(value)?;
+        fs::write(&self.path, bytes)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notes::{
+        links::{LinkKind, NoteLink},
+        long::LongNote,
+        note::Note,
+        short::ShortNote,
+        view::View,
+    };
+
+    /// A `View` exercising both `Note` variants, a link between them, a
+    /// trashed note, and a pending undo action, so a round trip has to
+    /// carry everything a real save would.
+    fn sample_view() -> View {
+        let short = Note::Short(ShortNote::new_headless("Buy milk".to_string(), None));
+
+        let mut long = LongNote::new_headless(
+            "Write report".to_string(),
+            Some("Quarterly numbers".to_string()),
+            None,
+        );
+        long.links.push(NoteLink {
+            target_id: short.id(),
+            kind: LinkKind::DependsOn,
+        });
+
+        let mut view = View::new_from_vec("round-trip", vec![short, Note::Long(long)]);
+
+        // add_note records an undo action; remove_note soft-deletes into trash.
+        view.add_note(Note::Short(ShortNote::new_headless(
+            "Call dentist".to_string(),
+            None,
+        )));
+        let stale_index = view.find_note_index("Buy milk").unwrap();
+        view.remove_note(stale_index);
+
+        view
+    }
+
+    fn assert_round_trips(format: Format, file_name: &str) {
+        let storage = Storage {
+            path: env::temp_dir().join(file_name),
+            format,
+        };
+        let view = sample_view();
+
+        storage.save(&view).unwrap();
+        let loaded: View = storage.load().unwrap();
+        let _ = fs::remove_file(&storage.path);
+
+        assert_eq!(loaded.notes().len(), 2);
+        assert!(loaded
+            .notes()
+            .iter()
+            .any(|note| note.title() == "Call dentist"));
+
+        let report = loaded
+            .notes()
+            .iter()
+            .find(|note| note.title() == "Write report")
+            .expect("long note survived the round trip");
+        assert_eq!(report.links().len(), 1);
+        assert_eq!(report.links()[0].kind, LinkKind::DependsOn);
+
+        assert_eq!(loaded.trash().len(), 1);
+        assert_eq!(loaded.trash()[0].0.title(), "Buy milk");
+
+        assert!(loaded.can_undo());
+    }
+
+    #[test]
+    fn round_trips_json() {
+        assert_round_trips(Format::Json, "notes_roundtrip_test.json");
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn round_trips_ron() {
+        assert_round_trips(Format::Ron, "notes_roundtrip_test.ron");
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn round_trips_cbor() {
+        assert_round_trips(Format::Cbor, "notes_roundtrip_test.cbor");
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn round_trips_bincode() {
+        assert_round_trips(Format::Bincode, "notes_roundtrip_test.bin");
+    }
+}
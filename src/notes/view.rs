@@ -1,21 +1,35 @@
 use std::{
     borrow::Cow,
+    env,
     fmt::{Display, Error},
-    fs,
-    path::Path,
 };
 
 use anyhow::{bail, Result};
+use chrono::NaiveDate;
 use inquire::{InquireError, Select, Text};
 use ptree::{print_tree, TreeItem};
 
-use super::note::Note;
+use super::{
+    history::{Action, History},
+    links::{self, LinkKind, NoteLink},
+    note::Note,
+    storage::Storage,
+};
+
+const TRASH_RETENTION_DAYS_ENV: &str = "NOTES_TRASH_RETENTION_DAYS";
+const DEFAULT_TRASH_RETENTION_DAYS: i64 = 30;
+const DEFAULT_VIEW_NAME: &str = "notes";
 
 #[derive(serde::Deserialize, serde::Serialize, Clone)]
 pub enum ViewState {
     Add,
     View,
     Tree,
+    Search,
+    Undo,
+    Redo,
+    Trash,
+    Links,
     Main,
     Remove,
     Exit,
@@ -28,6 +42,11 @@ impl Display for ViewState {
             ViewState::Add => write!(f, "Add note"),
             ViewState::Remove => write!(f, "Delete note"),
             ViewState::View => write!(f, "View notes"),
+            ViewState::Search => write!(f, "Search notes"),
+            ViewState::Undo => write!(f, "Undo"),
+            ViewState::Redo => write!(f, "Redo"),
+            ViewState::Trash => write!(f, "View trash"),
+            ViewState::Links => write!(f, "Manage links"),
             ViewState::Main => write!(f, "Goto main menu"),
             ViewState::Exit => write!(f, "Exit"),
             ViewState::Update(_) => Err(Error),
@@ -36,11 +55,45 @@ impl Display for ViewState {
     }
 }
 
+enum TrashChoice {
+    Restore,
+    Purge,
+}
+
+impl Display for TrashChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            TrashChoice::Restore => write!(f, "Restore"),
+            TrashChoice::Purge => write!(f, "Purge permanently"),
+        }
+    }
+}
+
+enum LinkAction {
+    Add,
+    Remove,
+    ViewBlockers,
+}
+
+impl Display for LinkAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            LinkAction::Add => write!(f, "Add link"),
+            LinkAction::Remove => write!(f, "Remove link"),
+            LinkAction::ViewBlockers => write!(f, "View transitive blockers"),
+        }
+    }
+}
+
 #[derive(serde::Deserialize, serde::Serialize, Clone)]
 pub struct View {
     name: String,
     notes: Vec<Note>,
     state: ViewState,
+    #[serde(default)]
+    history: History,
+    #[serde(default)]
+    trash: Vec<(Note, NaiveDate)>,
 }
 
 impl TreeItem for View {
@@ -54,26 +107,43 @@ impl TreeItem for View {
         write!(f, "{}", style.paint(&self.name))
     }
 
-    fn children(&self) -> std::borrow::Cow<[Self::Child]> {
+    fn children(&self) -> std::borrow::Cow<'_, [Self::Child]> {
         Cow::from(self.notes.clone())
     }
 }
 
-const VIEW_FILE_PATH: &str = "./notes_view.json";
-
 impl View {
     pub fn new() -> Result<View> {
-        let file_path = Path::new(VIEW_FILE_PATH);
-        if file_path.exists() {
+        Self::new_impl(true)
+    }
+
+    /// Like [`View::new`], but never prompts. Used by non-interactive CLI
+    /// subcommands, which must work the first time against a fresh
+    /// environment without a TTY to read a name from.
+    pub fn new_headless() -> Result<View> {
+        Self::new_impl(false)
+    }
+
+    fn new_impl(interactive: bool) -> Result<View> {
+        let storage = Storage::from_env();
+        if storage.exists() {
             // Ensure that we always start in main view
-            let mut loaded_view = View::load_from_file()?;
+            let mut loaded_view = View::load_from_file(&storage)?;
             loaded_view.state = ViewState::Main;
+            loaded_view.purge_expired_trash();
             Ok(loaded_view)
         } else {
+            let name = if interactive {
+                Text::new("Enter name for notes:").prompt()?
+            } else {
+                DEFAULT_VIEW_NAME.to_string()
+            };
             Ok(View {
-                name: Text::new("Enter name for notes:").prompt()?,
+                name,
                 notes: Vec::new(),
                 state: ViewState::Main,
+                history: History::default(),
+                trash: Vec::new(),
             })
         }
     }
@@ -81,8 +151,10 @@ impl View {
     pub fn new_from_vec(name: &str, notes: Vec<Note>) -> View {
         View {
             name: name.to_string(),
-            notes: notes,
+            notes,
             state: ViewState::Main,
+            history: History::default(),
+            trash: Vec::new(),
         }
     }
 
@@ -90,17 +162,76 @@ impl View {
         self.notes.clone()
     }
 
-    fn load_from_file() -> Result<View> {
-        let file = fs::read(VIEW_FILE_PATH)?;
-        Ok(serde_json::from_slice(&file)?)
+    pub fn notes(&self) -> &[Note] {
+        &self.notes
     }
 
-    pub fn save(&self) -> Result<()> {
-        let file = serde_json::to_vec(self)?;
-        fs::write(VIEW_FILE_PATH, file)?;
+    #[cfg(test)]
+    pub fn trash(&self) -> &[(Note, NaiveDate)] {
+        &self.trash
+    }
+
+    #[cfg(test)]
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    pub fn add_note(&mut self, note: Note) {
+        let id = note.id();
+        self.notes.push(note);
+        self.history.record(Action::Remove(id));
+    }
+
+    /// Resolve a note by 1-based index or by a case-insensitive title match.
+    pub fn find_note_index(&self, query: &str) -> Option<usize> {
+        if let Ok(index) = query.parse::<usize>() {
+            if index >= 1 && index <= self.notes.len() {
+                return Some(index - 1);
+            }
+        }
+
+        self.notes
+            .iter()
+            .position(|note| note.title().eq_ignore_ascii_case(query))
+    }
+
+    pub fn note_mut(&mut self, index: usize) -> Option<&mut Note> {
+        self.notes.get_mut(index)
+    }
+
+    /// Soft-delete: move the note into the trash bin instead of losing it
+    /// outright. Removals are restored via `ViewState::Trash` rather than
+    /// the generic undo stack, so they don't end up tracked in both places.
+    pub fn remove_note(&mut self, index: usize) -> Note {
+        let note = self.notes.remove(index);
+        self.trash
+            .push((note.clone(), chrono::Utc::now().naive_local().date()));
+        note
+    }
+
+    /// Drop any trash entries older than `NOTES_TRASH_RETENTION_DAYS` (30
+    /// days by default).
+    fn purge_expired_trash(&mut self) {
+        let retention = chrono::Duration::days(trash_retention_days());
+        let today = chrono::Utc::now().naive_local().date();
+        self.trash
+            .retain(|(_, deleted_at)| today - *deleted_at <= retention);
+    }
+
+    /// Print the note tree without going through the interactive menu.
+    pub fn print_tree(&self) -> Result<()> {
+        print_tree(self)?;
         Ok(())
     }
 
+    fn load_from_file(storage: &Storage) -> Result<View> {
+        storage.load()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        Storage::from_env().save(self)
+    }
+
     pub fn render(&mut self) -> Result<()> {
         match self.state {
             ViewState::Main => self.render_main(),
@@ -109,6 +240,11 @@ impl View {
             ViewState::Remove => self.render_remove_note(),
             ViewState::Update(index) => self.render_update_note(index),
             ViewState::Tree => self.render_tree(),
+            ViewState::Search => self.render_search(),
+            ViewState::Undo => self.render_undo(),
+            ViewState::Redo => self.render_redo(),
+            ViewState::Trash => self.render_trash(),
+            ViewState::Links => self.render_links(),
             ViewState::Exit => {
                 self.save()?;
                 Ok(())
@@ -120,12 +256,28 @@ impl View {
         let mut options: Vec<ViewState> = Vec::new();
 
         // don't show the option to view notes if we don't have any
-        if self.notes.len() > 0 {
-            options.append(&mut vec![ViewState::View, ViewState::Tree]);
+        if !self.notes.is_empty() {
+            options.append(&mut vec![
+                ViewState::View,
+                ViewState::Tree,
+                ViewState::Search,
+                ViewState::Links,
+            ]);
         };
-        let mut other_options = vec![ViewState::Add, ViewState::Remove, ViewState::Exit];
+        let mut other_options = vec![ViewState::Add, ViewState::Remove];
         options.append(&mut other_options);
 
+        if self.history.can_undo() {
+            options.push(ViewState::Undo);
+        }
+        if self.history.can_redo() {
+            options.push(ViewState::Redo);
+        }
+        if !self.trash.is_empty() {
+            options.push(ViewState::Trash);
+        }
+        options.push(ViewState::Exit);
+
         match Select::new("Choose action", options).prompt() {
             Ok(action) => {
                 self.state = action;
@@ -140,7 +292,7 @@ impl View {
 
     fn render_add_note(&mut self) -> Result<()> {
         match Note::new() {
-            Ok(note) => self.notes.push(note),
+            Ok(note) => self.add_note(note),
             Err(e) => match e.downcast_ref() {
                 Some(InquireError::OperationCanceled)
                 | Some(InquireError::OperationInterrupted) => (),
@@ -148,7 +300,7 @@ impl View {
             },
         };
 
-        self.to_menu()
+        self.goto_menu()
     }
 
     fn render_view_notes(&mut self) -> Result<()> {
@@ -169,7 +321,7 @@ impl View {
                 self.render()
             }
             Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => {
-                self.to_menu()
+                self.goto_menu()
             }
             Err(e) => bail!(e),
         }
@@ -189,18 +341,20 @@ impl View {
                     .iter()
                     .position(|s| *s == choice_str)
                     .unwrap();
-                self.notes.remove(index);
+                self.remove_note(index);
             }
             Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => (),
             Err(e) => bail!(e),
         };
 
-        self.to_menu()
+        self.goto_menu()
     }
 
     fn render_update_note(&mut self, index: usize) -> Result<()> {
+        let previous = self.notes.get(index).unwrap().clone();
+        let previous_id = previous.id();
         match self.notes.get_mut(index).unwrap().update() {
-            Ok(()) => (),
+            Ok(()) => self.history.record(Action::Replace(previous_id, previous)),
             Err(e) => match e.downcast_ref() {
                 Some(InquireError::OperationCanceled)
                 | Some(InquireError::OperationInterrupted) => (),
@@ -208,16 +362,295 @@ impl View {
             },
         };
 
-        self.to_menu()
+        self.goto_menu()
     }
 
     fn render_tree(&mut self) -> Result<()> {
-        print_tree(self)?;
-        self.to_menu()
+        self.print_tree()?;
+        self.goto_menu()
     }
 
-    fn to_menu(&mut self) -> Result<()> {
+    fn render_search(&mut self) -> Result<()> {
+        let query = match Text::new("Search notes:").prompt() {
+            Ok(query) => query,
+            Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => {
+                return self.goto_menu()
+            }
+            Err(e) => bail!(e),
+        };
+
+        let mut matches: Vec<(usize, i64)> = self
+            .notes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, note)| note.search_score(&query).map(|score| (index, score)))
+            .collect();
+        matches.sort_by_key(|m| std::cmp::Reverse(m.1));
+
+        if matches.is_empty() {
+            println!("No notes matched '{}'", query);
+            return self.goto_menu();
+        }
+
+        let render_context: Vec<_> = matches
+            .iter()
+            .map(|(index, _)| self.notes[*index].render())
+            .collect();
+        let choice = Select::new(
+            "Select a note or press esc to return",
+            render_context.clone(),
+        )
+        .prompt();
+
+        match choice {
+            Ok(choice_str) => {
+                let position = render_context
+                    .iter()
+                    .position(|s| *s == choice_str)
+                    .unwrap();
+                self.state = ViewState::Update(matches[position].0);
+                self.render()
+            }
+            Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => {
+                self.goto_menu()
+            }
+            Err(e) => bail!(e),
+        }
+    }
+
+    fn render_trash(&mut self) -> Result<()> {
+        if self.trash.is_empty() {
+            println!("Trash is empty");
+            return self.goto_menu();
+        }
+
+        let render_context: Vec<_> = self
+            .trash
+            .iter()
+            .map(|(note, deleted_at)| format!("{} (deleted {})", note.render(), deleted_at))
+            .collect();
+
+        let choice = Select::new(
+            "Select a trashed note or press esc to return",
+            render_context.clone(),
+        )
+        .prompt();
+
+        let index = match choice {
+            Ok(choice_str) => render_context
+                .iter()
+                .position(|s| *s == choice_str)
+                .unwrap(),
+            Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => {
+                return self.goto_menu()
+            }
+            Err(e) => bail!(e),
+        };
+
+        let action = Select::new(
+            "Restore or purge?",
+            vec![TrashChoice::Restore, TrashChoice::Purge],
+        )
+        .prompt();
+
+        match action {
+            Ok(TrashChoice::Restore) => {
+                let (note, _) = self.trash.remove(index);
+                self.notes.push(note);
+            }
+            Ok(TrashChoice::Purge) => {
+                self.trash.remove(index);
+            }
+            Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => (),
+            Err(e) => bail!(e),
+        };
+
+        self.goto_menu()
+    }
+
+    fn render_links(&mut self) -> Result<()> {
+        let action = Select::new(
+            "Choose link action",
+            vec![LinkAction::Add, LinkAction::Remove, LinkAction::ViewBlockers],
+        )
+        .prompt();
+
+        match action {
+            Ok(LinkAction::Add) => self.add_link()?,
+            Ok(LinkAction::Remove) => self.remove_link()?,
+            Ok(LinkAction::ViewBlockers) => self.view_blockers()?,
+            Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => (),
+            Err(e) => bail!(e),
+        };
+
+        self.goto_menu()
+    }
+
+    /// Prompt for a note, a relation, and a target note, then add the
+    /// link — rejecting it if it would introduce a cycle in the
+    /// `Blocks`/`DependsOn` dependency graph.
+    fn add_link(&mut self) -> Result<()> {
+        if self.notes.len() < 2 {
+            println!("Need at least two notes to add a link");
+            return Ok(());
+        }
+
+        let from_index = match self.select_note("Link from which note?")? {
+            Some(index) => index,
+            None => return Ok(()),
+        };
+        let from_id = self.notes[from_index].id();
+
+        let kind = match Select::new(
+            "Relation",
+            vec![LinkKind::Blocks, LinkKind::DependsOn, LinkKind::RelatesTo],
+        )
+        .prompt()
+        {
+            Ok(kind) => kind,
+            Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => {
+                return Ok(())
+            }
+            Err(e) => bail!(e),
+        };
+
+        let to_index = match self.select_note_excluding("Link to which note?", from_index)? {
+            Some(index) => index,
+            None => return Ok(()),
+        };
+
+        let link = NoteLink {
+            target_id: self.notes[to_index].id(),
+            kind,
+        };
+
+        if links::creates_cycle(&self.notes, from_id, &link) {
+            println!("That link would create a dependency cycle; not adding it");
+            return Ok(());
+        }
+
+        self.notes[from_index].add_link(link);
+        Ok(())
+    }
+
+    fn remove_link(&mut self) -> Result<()> {
+        let from_index = match self.select_note("Remove a link from which note?")? {
+            Some(index) => index,
+            None => return Ok(()),
+        };
+
+        let note_links = self.notes[from_index].links().to_vec();
+        if note_links.is_empty() {
+            println!("That note has no links");
+            return Ok(());
+        }
+
+        let render_context: Vec<_> = note_links.iter().map(|link| link.to_string()).collect();
+        let choice = Select::new("Select a link to remove", render_context.clone()).prompt();
+
+        match choice {
+            Ok(choice_str) => {
+                let index = render_context
+                    .iter()
+                    .position(|s| *s == choice_str)
+                    .unwrap();
+                self.notes[from_index].remove_link(index);
+            }
+            Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => (),
+            Err(e) => bail!(e),
+        };
+
+        Ok(())
+    }
+
+    fn view_blockers(&mut self) -> Result<()> {
+        let index = match self.select_note("View blockers for which note?")? {
+            Some(index) => index,
+            None => return Ok(()),
+        };
+
+        let blocker_ids = links::transitive_blockers(&self.notes, self.notes[index].id());
+        if blocker_ids.is_empty() {
+            println!("{} has no blockers", self.notes[index].render());
+            return Ok(());
+        }
+
+        println!("Blockers for {}:", self.notes[index].render());
+        for blocker_id in blocker_ids {
+            if let Some(note) = self.notes.iter().find(|note| note.id() == blocker_id) {
+                println!("  {}", note.render());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Prompt the user to pick a note, returning `None` if they cancel.
+    fn select_note(&self, message: &str) -> Result<Option<usize>> {
+        self.select_note_from(message, &self.notes.iter().collect::<Vec<_>>())
+    }
+
+    /// Like `select_note`, but omits the note at `excluded_index`.
+    fn select_note_excluding(
+        &self,
+        message: &str,
+        excluded_index: usize,
+    ) -> Result<Option<usize>> {
+        let candidates: Vec<_> = self
+            .notes
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != excluded_index)
+            .map(|(_, note)| note)
+            .collect();
+        self.select_note_from(message, &candidates)
+    }
+
+    fn select_note_from(&self, message: &str, candidates: &[&Note]) -> Result<Option<usize>> {
+        let render_context: Vec<_> = candidates.iter().map(|note| note.render()).collect();
+        let choice = Select::new(message, render_context.clone()).prompt();
+
+        match choice {
+            Ok(choice_str) => {
+                let chosen = candidates[render_context
+                    .iter()
+                    .position(|s| *s == choice_str)
+                    .unwrap()];
+                Ok(self.notes.iter().position(|note| note.id() == chosen.id()))
+            }
+            Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => {
+                Ok(None)
+            }
+            Err(e) => bail!(e),
+        }
+    }
+
+    fn render_undo(&mut self) -> Result<()> {
+        if !self.history.undo(&mut self.notes) {
+            println!("Nothing to undo");
+        }
+
+        self.goto_menu()
+    }
+
+    fn render_redo(&mut self) -> Result<()> {
+        if !self.history.redo(&mut self.notes) {
+            println!("Nothing to redo");
+        }
+
+        self.goto_menu()
+    }
+
+    fn goto_menu(&mut self) -> Result<()> {
         self.state = ViewState::Main;
         self.render()
     }
 }
+
+fn trash_retention_days() -> i64 {
+    env::var(TRASH_RETENTION_DAYS_ENV)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_TRASH_RETENTION_DAYS)
+}
+